@@ -0,0 +1,15 @@
+use futures::{Future, Poll};
+use tokio_io::{AsyncRead, AsyncWrite};
+
+/// A service that establishes a connection to a target address.
+pub trait ConnectService<A> {
+    type Response: AsyncRead + AsyncWrite;
+    type Error;
+    type Future: Future<Item = Self::Response, Error = Self::Error>;
+
+    /// Returns `Ready` when the service is able to process a `connect`
+    /// call, the way `tower_service::Service::poll_ready` does.
+    fn poll_ready(&mut self) -> Poll<(), Self::Error>;
+
+    fn connect(&mut self, target: A) -> Self::Future;
+}
@@ -0,0 +1,359 @@
+use futures::{Async, Future, Poll};
+use std::error::Error;
+use std::fmt;
+use std::iter::Peekable;
+use std::mem;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use std::vec::IntoIter;
+use tokio_timer::Delay;
+
+use crate::connect::ConnectService;
+use crate::resolve::{MaybeResolved, ResolveAll};
+
+/// How long to wait for a connection attempt before racing the next
+/// address in the list, per RFC 8305.
+const HAPPY_EYEBALLS_DELAY: Duration = Duration::from_millis(250);
+
+pub struct Connector<C, R, Target>
+where
+    C: ConnectService<SocketAddr> + Clone,
+    R: ResolveAll<Target>,
+{
+    connect: C,
+    resolver: R,
+    _pd: std::marker::PhantomData<Target>,
+}
+
+impl<C, R, Target> Connector<C, R, Target>
+where
+    C: ConnectService<SocketAddr> + Clone,
+    R: ResolveAll<Target>,
+{
+    pub fn new(connect: C, resolver: R) -> Self {
+        Connector {
+            connect,
+            resolver,
+            _pd: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<C, R, Target> ConnectService<Target> for Connector<C, R, Target>
+where
+    C: ConnectService<SocketAddr> + Clone,
+    R: ResolveAll<Target>,
+    Target: MaybeResolved,
+{
+    type Response = C::Response;
+    type Error = ConnectorError<C, R, Target>;
+    type Future = ConnectFuture<C, R, Target>;
+
+    fn poll_ready(&mut self) -> Poll<(), Self::Error> {
+        match self.resolver.poll_ready() {
+            Ok(Async::Ready(())) => {}
+            Ok(Async::NotReady) => return Ok(Async::NotReady),
+            Err(e) => return Err(ConnectorError::Resolve(e)),
+        }
+
+        self.connect.poll_ready().map_err(ConnectorError::Connect)
+    }
+
+    fn connect(&mut self, target: Target) -> Self::Future {
+        // Already know the address (an IP literal, or a caller-supplied
+        // pre-resolved endpoint) — skip the resolver entirely.
+        if let Some(addr) = target.maybe_resolved() {
+            return ConnectFuture {
+                state: State::WaitReady(vec![addr]),
+                connector: self.connect.clone(),
+            };
+        }
+
+        ConnectFuture {
+            state: State::Resolving(self.resolver.lookup_all(target)),
+            connector: self.connect.clone(),
+        }
+    }
+}
+
+pub enum ConnectorError<C, R, Target>
+where
+    C: ConnectService<SocketAddr>,
+    R: ResolveAll<Target>,
+{
+    Resolve(R::Error),
+    Connect(C::Error),
+    /// The resolver returned zero addresses for the target.
+    NoAddresses,
+}
+
+impl<C, R, Target> fmt::Debug for ConnectorError<C, R, Target>
+where
+    C: ConnectService<SocketAddr>,
+    R: ResolveAll<Target>,
+    C::Error: fmt::Debug,
+    R::Error: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConnectorError::Resolve(e) => f.debug_tuple("Resolve").field(e).finish(),
+            ConnectorError::Connect(e) => f.debug_tuple("Connect").field(e).finish(),
+            ConnectorError::NoAddresses => f.debug_tuple("NoAddresses").finish(),
+        }
+    }
+}
+
+impl<C, R, Target> fmt::Display for ConnectorError<C, R, Target>
+where
+    C: ConnectService<SocketAddr>,
+    R: ResolveAll<Target>,
+    C::Error: fmt::Display,
+    R::Error: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConnectorError::Resolve(e) => write!(f, "failed to resolve target: {}", e),
+            ConnectorError::Connect(e) => write!(f, "failed to connect: {}", e),
+            ConnectorError::NoAddresses => write!(f, "resolver returned no addresses to connect to"),
+        }
+    }
+}
+
+impl<C, R, Target> Error for ConnectorError<C, R, Target>
+where
+    C: ConnectService<SocketAddr>,
+    R: ResolveAll<Target>,
+    C::Error: Error + 'static,
+    R::Error: Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConnectorError::Resolve(e) => Some(e),
+            ConnectorError::Connect(e) => Some(e),
+            ConnectorError::NoAddresses => None,
+        }
+    }
+}
+
+pub struct ConnectFuture<C, R, Target>
+where
+    C: ConnectService<SocketAddr>,
+    R: ResolveAll<Target>,
+{
+    state: State<C, R, Target>,
+    connector: C,
+}
+
+enum State<C, R, Target>
+where
+    C: ConnectService<SocketAddr>,
+    R: ResolveAll<Target>,
+{
+    Resolving(R::AllFuture),
+    WaitReady(Vec<SocketAddr>),
+    Connecting(Connecting<C>),
+    Done,
+}
+
+impl<C, R, Target> Future for ConnectFuture<C, R, Target>
+where
+    C: ConnectService<SocketAddr>,
+    R: ResolveAll<Target>,
+{
+    type Item = C::Response;
+    type Error = ConnectorError<C, R, Target>;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            match mem::replace(&mut self.state, State::Done) {
+                State::Resolving(mut fut) => match fut.poll() {
+                    Ok(Async::Ready(addrs)) => {
+                        self.state = State::WaitReady(addrs);
+                    }
+                    Ok(Async::NotReady) => {
+                        self.state = State::Resolving(fut);
+                        return Ok(Async::NotReady);
+                    }
+                    Err(e) => return Err(ConnectorError::Resolve(e)),
+                },
+                // Addresses are in hand, but the connector may have gone
+                // unready while we were resolving — check again before
+                // transitioning into connecting.
+                State::WaitReady(addrs) => {
+                    if addrs.is_empty() {
+                        return Err(ConnectorError::NoAddresses);
+                    }
+                    match self.connector.poll_ready() {
+                        Ok(Async::Ready(())) => {
+                            self.state =
+                                State::Connecting(Connecting::new(addrs, &mut self.connector));
+                        }
+                        Ok(Async::NotReady) => {
+                            self.state = State::WaitReady(addrs);
+                            return Ok(Async::NotReady);
+                        }
+                        Err(e) => return Err(ConnectorError::Connect(e)),
+                    }
+                }
+                State::Connecting(mut connecting) => {
+                    match connecting.poll(&mut self.connector) {
+                        Ok(Async::Ready(resp)) => return Ok(Async::Ready(resp)),
+                        Ok(Async::NotReady) => {
+                            self.state = State::Connecting(connecting);
+                            return Ok(Async::NotReady);
+                        }
+                        Err(e) => return Err(ConnectorError::Connect(e)),
+                    }
+                }
+                State::Done => panic!("ConnectFuture polled after completion"),
+            }
+        }
+    }
+}
+
+/// Races connection attempts against an ordered list of addresses,
+/// per Happy Eyeballs (RFC 8305): the first address is dialed
+/// immediately, and if it hasn't completed within
+/// `HAPPY_EYEBALLS_DELAY` the next address is dialed concurrently
+/// while the earlier attempt stays in flight. The first attempt to
+/// succeed wins; the rest are dropped. If every address fails, the
+/// last error observed is returned.
+///
+/// With a single address this degenerates to the original
+/// resolve-then-connect fast path: no delay timer is ever armed.
+struct Connecting<C>
+where
+    C: ConnectService<SocketAddr>,
+{
+    addrs: Peekable<IntoIter<SocketAddr>>,
+    attempts: Vec<C::Future>,
+    delay: Option<Delay>,
+    last_err: Option<C::Error>,
+}
+
+impl<C> Connecting<C>
+where
+    C: ConnectService<SocketAddr>,
+{
+    fn new(mut addrs: Vec<SocketAddr>, connector: &mut C) -> Self {
+        sort_addrs(&mut addrs);
+
+        let mut addrs = addrs.into_iter().peekable();
+        let mut attempts = Vec::new();
+        let mut delay = None;
+
+        if let Some(addr) = addrs.next() {
+            attempts.push(connector.connect(addr));
+            if addrs.peek().is_some() {
+                delay = Some(Delay::new(Instant::now() + HAPPY_EYEBALLS_DELAY));
+            }
+        }
+
+        Connecting {
+            addrs,
+            attempts,
+            delay,
+            last_err: None,
+        }
+    }
+
+    /// Starts racing the next address, gated on the connector's own
+    /// `poll_ready` like every other `connect` call in this crate.
+    /// Arms a fresh stagger delay if another address remains after it.
+    ///
+    /// Returns `Ok(true)` if an attempt was started, in which case the
+    /// caller should `continue` its poll loop so a freshly armed delay
+    /// gets polled (and its waker registered) before `poll` returns.
+    fn start_next(&mut self, connector: &mut C) -> Result<bool, C::Error> {
+        if self.addrs.peek().is_none() {
+            return Ok(false);
+        }
+
+        match connector.poll_ready()? {
+            Async::NotReady => return Ok(false),
+            Async::Ready(()) => {}
+        }
+
+        let addr = self.addrs.next().expect("just peeked Some");
+        self.attempts.push(connector.connect(addr));
+        if self.addrs.peek().is_some() {
+            self.delay = Some(Delay::new(Instant::now() + HAPPY_EYEBALLS_DELAY));
+        }
+        Ok(true)
+    }
+
+    fn poll(&mut self, connector: &mut C) -> Poll<C::Response, C::Error> {
+        loop {
+            let mut fire = false;
+            if let Some(ref mut delay) = self.delay {
+                match delay.poll() {
+                    Ok(Async::Ready(())) => fire = true,
+                    Ok(Async::NotReady) => {}
+                    // A broken timer shouldn't stall the race; fall back to
+                    // starting the next attempt right away.
+                    Err(_) => fire = true,
+                }
+            }
+
+            if fire {
+                self.delay = None;
+                // Loop back around so a freshly armed delay gets polled
+                // (and its waker registered) before we return `NotReady`
+                // — `Delay` only arranges a wakeup on `poll`.
+                if self.start_next(connector)? {
+                    continue;
+                }
+            }
+
+            let mut i = 0;
+            while i < self.attempts.len() {
+                match self.attempts[i].poll() {
+                    Ok(Async::Ready(resp)) => return Ok(Async::Ready(resp)),
+                    Ok(Async::NotReady) => i += 1,
+                    Err(e) => {
+                        self.last_err = Some(e);
+                        self.attempts.remove(i);
+                    }
+                }
+            }
+
+            // RFC 8305: move on to the next candidate as soon as every
+            // in-flight attempt has failed, rather than waiting out
+            // whatever is left of the stagger delay.
+            if self.attempts.is_empty() {
+                self.delay = None;
+                if self.addrs.peek().is_none() {
+                    return Err(self
+                        .last_err
+                        .take()
+                        .expect("Connecting polled with no addresses left to try"));
+                }
+                if self.start_next(connector)? {
+                    continue;
+                }
+            }
+
+            return Ok(Async::NotReady);
+        }
+    }
+}
+
+/// Orders addresses so that families alternate starting with IPv6,
+/// per RFC 8305's interleaving recommendation.
+fn sort_addrs(addrs: &mut Vec<SocketAddr>) {
+    let (v6, v4): (Vec<_>, Vec<_>) = addrs.drain(..).partition(|addr| addr.is_ipv6());
+    let mut v6 = v6.into_iter();
+    let mut v4 = v4.into_iter();
+
+    loop {
+        match (v6.next(), v4.next()) {
+            (Some(a), Some(b)) => {
+                addrs.push(a);
+                addrs.push(b);
+            }
+            (Some(a), None) => addrs.push(a),
+            (None, Some(b)) => addrs.push(b),
+            (None, None) => break,
+        }
+    }
+}
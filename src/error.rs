@@ -0,0 +1,58 @@
+use futures::{Future, Poll};
+use std::error::Error as StdError;
+
+use crate::connect::ConnectService;
+
+/// The `Box<dyn Error + Send + Sync>` convention used by crates like
+/// `tower-reconnect` for heterogeneous middleware stacks.
+pub type BoxError = Box<dyn StdError + Send + Sync>;
+
+/// Erases a `ConnectService`'s error into a `BoxError`, so it drops
+/// cleanly into a stack without callers having to name the wrapped
+/// service's concrete error type (e.g. `ConnectorError<C, R, Target>`).
+pub struct BoxConnectError<C> {
+    inner: C,
+}
+
+impl<C> BoxConnectError<C> {
+    pub fn new(inner: C) -> Self {
+        BoxConnectError { inner }
+    }
+}
+
+impl<C, Target> ConnectService<Target> for BoxConnectError<C>
+where
+    C: ConnectService<Target>,
+    C::Error: StdError + Send + Sync + 'static,
+{
+    type Response = C::Response;
+    type Error = BoxError;
+    type Future = BoxConnectErrorFuture<C::Future>;
+
+    fn poll_ready(&mut self) -> Poll<(), Self::Error> {
+        self.inner.poll_ready().map_err(Into::into)
+    }
+
+    fn connect(&mut self, target: Target) -> Self::Future {
+        BoxConnectErrorFuture {
+            inner: self.inner.connect(target),
+        }
+    }
+}
+
+pub struct BoxConnectErrorFuture<F> {
+    inner: F,
+}
+
+impl<F> Future for BoxConnectErrorFuture<F>
+where
+    F: Future,
+    F::Error: StdError + Send + Sync + 'static,
+{
+    type Item = F::Item;
+    type Error = BoxError;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        self.inner.poll().map_err(Into::into)
+    }
+}
@@ -0,0 +1,105 @@
+use futures::future::{self, FutureResult};
+use futures::{Async, Future, Poll};
+use std::mem;
+
+use crate::connect::ConnectService;
+
+/// Wraps a `ConnectService<Target>` into a long-lived service that
+/// transparently re-resolves and re-dials when a connection attempt
+/// errors, the way `tower_reconnect::Reconnect` does for
+/// request/response services.
+///
+/// `poll_ready` drives the dial: once the wrapped `ConnectService`
+/// itself reports `Ready`, it resolves and connects to the stored
+/// target, reusing that service on every attempt (rather than pinning
+/// to a stale `SocketAddr`) so reconnects pick up endpoint changes
+/// such as a rolling deployment. `connect` hands over the established
+/// connection once it's ready, then resets to `Idle` so the next
+/// cycle dials fresh.
+pub struct Reconnect<C, Target>
+where
+    C: ConnectService<Target>,
+{
+    connect: C,
+    target: Target,
+    state: State<C, Target>,
+}
+
+enum State<C, Target>
+where
+    C: ConnectService<Target>,
+{
+    Idle,
+    Connecting(C::Future),
+    Connected(C::Response),
+}
+
+impl<C, Target> Reconnect<C, Target>
+where
+    C: ConnectService<Target>,
+    Target: Clone,
+{
+    pub fn new(connect: C, target: Target) -> Self {
+        Reconnect {
+            connect,
+            target,
+            state: State::Idle,
+        }
+    }
+}
+
+impl<C, Target> ConnectService<()> for Reconnect<C, Target>
+where
+    C: ConnectService<Target>,
+    Target: Clone,
+{
+    type Response = C::Response;
+    type Error = C::Error;
+    type Future = FutureResult<C::Response, C::Error>;
+
+    fn poll_ready(&mut self) -> Poll<(), Self::Error> {
+        loop {
+            // Swap in `Idle` as a placeholder: on the error path below this
+            // leaves `self.state` reset to `Idle` with no extra bookkeeping,
+            // so the next `poll_ready` starts a fresh resolve+connect cycle.
+            match mem::replace(&mut self.state, State::Idle) {
+                // Drive the inner service to `Ready`, the way every other
+                // `connect` call in this crate does, before dialing —
+                // a rate-limited resolver or an exhausted fd budget needs
+                // to be able to hold reconnects back too.
+                State::Idle => match self.connect.poll_ready() {
+                    Ok(Async::Ready(())) => {
+                        self.state = State::Connecting(self.connect.connect(self.target.clone()));
+                    }
+                    Ok(Async::NotReady) => {
+                        self.state = State::Idle;
+                        return Ok(Async::NotReady);
+                    }
+                    Err(e) => return Err(e),
+                },
+                State::Connecting(mut fut) => match fut.poll() {
+                    Ok(Async::Ready(response)) => {
+                        self.state = State::Connected(response);
+                        return Ok(Async::Ready(()));
+                    }
+                    Ok(Async::NotReady) => {
+                        self.state = State::Connecting(fut);
+                        return Ok(Async::NotReady);
+                    }
+                    Err(e) => return Err(e),
+                },
+                State::Connected(response) => {
+                    self.state = State::Connected(response);
+                    return Ok(Async::Ready(()));
+                }
+            }
+        }
+    }
+
+    fn connect(&mut self, _target: ()) -> Self::Future {
+        match mem::replace(&mut self.state, State::Idle) {
+            State::Connected(response) => future::ok(response),
+            _ => panic!("Reconnect::connect called before poll_ready reported Ready"),
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use futures::{Async, Future, Poll};
+use std::net::SocketAddr;
+
+/// Represents a type that can resolve a `SocketAddr` from some
+/// type `Target`.
+pub trait Resolve<Target> {
+    type Error;
+    type Future: Future<Item = SocketAddr, Error = Self::Error>;
+
+    /// Returns `Ready` when the resolver is able to process a `lookup`
+    /// call, the way `tower_service::Service::poll_ready` does.
+    fn poll_ready(&mut self) -> Poll<(), Self::Error>;
+
+    fn lookup(&mut self, target: Target) -> Self::Future;
+}
+
+/// Extends `Resolve` with the ability to return every address a
+/// `Target` resolves to, ordered for Happy Eyeballs (RFC 8305)
+/// connection racing.
+///
+/// Resolvers backed by something that can actually return several
+/// addresses (e.g. A/AAAA records) should implement this directly.
+/// A resolver that only ever yields one address can be wrapped in
+/// `SingleResolve` to get a `ResolveAll` impl for free — there's no
+/// blanket impl here, since one covering every `Resolve` would make
+/// it impossible for any concrete resolver to provide its own
+/// multi-address `lookup_all` (E0119, no specialization on stable).
+pub trait ResolveAll<Target>: Resolve<Target> {
+    type AllFuture: Future<Item = Vec<SocketAddr>, Error = Self::Error>;
+
+    fn lookup_all(&mut self, target: Target) -> Self::AllFuture;
+}
+
+/// Adapts a single-address `Resolve` into a `ResolveAll` that yields
+/// a one-element `Vec`, for resolvers that can't (or don't need to)
+/// return multiple addresses.
+#[derive(Clone)]
+pub struct SingleResolve<R>(R);
+
+impl<R> SingleResolve<R> {
+    pub fn new(inner: R) -> Self {
+        SingleResolve(inner)
+    }
+}
+
+impl<R, Target> Resolve<Target> for SingleResolve<R>
+where
+    R: Resolve<Target>,
+{
+    type Error = R::Error;
+    type Future = R::Future;
+
+    fn poll_ready(&mut self) -> Poll<(), Self::Error> {
+        self.0.poll_ready()
+    }
+
+    fn lookup(&mut self, target: Target) -> Self::Future {
+        self.0.lookup(target)
+    }
+}
+
+impl<R, Target> ResolveAll<Target> for SingleResolve<R>
+where
+    R: Resolve<Target>,
+{
+    type AllFuture = LookupAll<R::Future>;
+
+    fn lookup_all(&mut self, target: Target) -> Self::AllFuture {
+        LookupAll(self.0.lookup(target))
+    }
+}
+
+/// Future returned by `SingleResolve`'s `ResolveAll` impl, adapting a
+/// single-address `Resolve::Future` into one that yields a `Vec`.
+pub struct LookupAll<F>(F);
+
+impl<F> Future for LookupAll<F>
+where
+    F: Future<Item = SocketAddr>,
+{
+    type Item = Vec<SocketAddr>;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        match self.0.poll()? {
+            Async::Ready(addr) => Ok(Async::Ready(vec![addr])),
+            Async::NotReady => Ok(Async::NotReady),
+        }
+    }
+}
+
+/// A resolve target that can report its hostname and an optional
+/// explicit port, and rebuild itself with a new host/port pair.
+///
+/// Adapters that need to inspect or rewrite what they resolve (e.g.
+/// `SrvResolve`) use this instead of requiring a concrete `Target`
+/// type.
+pub trait HostPort: Sized {
+    fn host(&self) -> &str;
+
+    fn port(&self) -> Option<u16>;
+
+    fn with_host_port(&self, host: &str, port: u16) -> Self;
+}
+
+/// A resolve target that may already carry a concrete `SocketAddr`
+/// (an IP literal, or an endpoint a caller pre-resolved themselves).
+///
+/// `Connector` uses this to skip the resolver entirely when the
+/// address is already known, avoiding a pointless DNS round-trip.
+pub trait MaybeResolved {
+    fn maybe_resolved(&self) -> Option<SocketAddr>;
+}
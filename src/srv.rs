@@ -0,0 +1,168 @@
+use futures::{Async, Future, Poll};
+use rand::Rng;
+use std::mem;
+use std::net::{IpAddr, SocketAddr};
+
+use crate::resolve::{HostPort, Resolve};
+
+/// A single SRV record, as returned by a `ResolveSrv`.
+#[derive(Clone, Debug)]
+pub struct SrvRecord {
+    pub priority: u16,
+    pub weight: u16,
+    pub port: u16,
+    pub target: String,
+}
+
+/// Represents a type that can query SRV records for a `Target`.
+pub trait ResolveSrv<Target> {
+    type Error;
+    type Future: Future<Item = Vec<SrvRecord>, Error = Self::Error>;
+
+    fn lookup_srv(&mut self, target: &Target) -> Self::Future;
+}
+
+/// Wraps a `Resolve` so that hostname targets are first resolved
+/// through SRV records, falling back to the wrapped resolver's usual
+/// A/AAAA lookup when no SRV records exist.
+///
+/// Targets that already carry an explicit port, or that are IP
+/// literals, skip the SRV query entirely and pass straight through to
+/// the inner resolver.
+pub struct SrvResolve<S, R> {
+    srv: S,
+    inner: R,
+}
+
+impl<S, R> SrvResolve<S, R> {
+    pub fn new(srv: S, inner: R) -> Self {
+        SrvResolve { srv, inner }
+    }
+}
+
+impl<S, R, Target> Resolve<Target> for SrvResolve<S, R>
+where
+    Target: HostPort,
+    S: ResolveSrv<Target>,
+    R: Resolve<Target> + Clone,
+{
+    type Error = SrvResolveError<S, R, Target>;
+    type Future = SrvResolveFuture<S, R, Target>;
+
+    fn poll_ready(&mut self) -> Poll<(), Self::Error> {
+        self.inner.poll_ready().map_err(SrvResolveError::Resolve)
+    }
+
+    fn lookup(&mut self, target: Target) -> Self::Future {
+        let bypass = target.port().is_some() || target.host().parse::<IpAddr>().is_ok();
+
+        let state = if bypass {
+            State::Inner(self.inner.lookup(target))
+        } else {
+            State::Srv(self.srv.lookup_srv(&target), target)
+        };
+
+        SrvResolveFuture {
+            state,
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+pub enum SrvResolveError<S, R, Target>
+where
+    S: ResolveSrv<Target>,
+    R: Resolve<Target>,
+{
+    Srv(S::Error),
+    Resolve(R::Error),
+}
+
+enum State<S, R, Target>
+where
+    S: ResolveSrv<Target>,
+    R: Resolve<Target>,
+{
+    Srv(S::Future, Target),
+    Inner(R::Future),
+    Done,
+}
+
+pub struct SrvResolveFuture<S, R, Target>
+where
+    S: ResolveSrv<Target>,
+    R: Resolve<Target>,
+{
+    state: State<S, R, Target>,
+    inner: R,
+}
+
+impl<S, R, Target> Future for SrvResolveFuture<S, R, Target>
+where
+    Target: HostPort,
+    S: ResolveSrv<Target>,
+    R: Resolve<Target>,
+{
+    type Item = SocketAddr;
+    type Error = SrvResolveError<S, R, Target>;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            match mem::replace(&mut self.state, State::Done) {
+                State::Srv(mut fut, target) => {
+                    let records = match fut.poll() {
+                        Ok(Async::Ready(records)) => records,
+                        Ok(Async::NotReady) => {
+                            self.state = State::Srv(fut, target);
+                            return Ok(Async::NotReady);
+                        }
+                        Err(e) => return Err(SrvResolveError::Srv(e)),
+                    };
+
+                    let resolved = match pick_srv_record(&records) {
+                        Some(record) => target.with_host_port(&record.target, record.port),
+                        None => target,
+                    };
+
+                    self.state = State::Inner(self.inner.lookup(resolved));
+                    continue;
+                }
+                State::Inner(mut fut) => {
+                    return match fut.poll() {
+                        Ok(Async::Ready(addr)) => Ok(Async::Ready(addr)),
+                        Ok(Async::NotReady) => {
+                            self.state = State::Inner(fut);
+                            Ok(Async::NotReady)
+                        }
+                        Err(e) => Err(SrvResolveError::Resolve(e)),
+                    };
+                }
+                State::Done => panic!("SrvResolveFuture polled after completion"),
+            }
+        }
+    }
+}
+
+/// Picks an SRV record honoring priority (lower wins) and weight
+/// (each record's odds are proportional to its weight plus one, so
+/// zero-weight records keep a small chance), per RFC 2782.
+fn pick_srv_record(records: &[SrvRecord]) -> Option<&SrvRecord> {
+    let min_priority = records.iter().map(|r| r.priority).min()?;
+    let candidates: Vec<&SrvRecord> = records
+        .iter()
+        .filter(|r| r.priority == min_priority)
+        .collect();
+
+    let total_weight: u32 = candidates.iter().map(|r| u32::from(r.weight) + 1).sum();
+    let mut choice = rand::thread_rng().gen_range(0, total_weight);
+
+    for record in &candidates {
+        let weight = u32::from(record.weight) + 1;
+        if choice < weight {
+            return Some(record);
+        }
+        choice -= weight;
+    }
+
+    candidates.first().copied()
+}
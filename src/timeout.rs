@@ -0,0 +1,146 @@
+use futures::{Async, Future, Poll};
+use std::time::{Duration, Instant};
+use tokio_timer::Delay;
+
+use crate::connect::ConnectService;
+use crate::resolve::Resolve;
+
+/// Bounds how long a `ConnectService` is allowed to take, mirroring
+/// the `TimeoutService` wrapper used by the actix-web connector.
+///
+/// This can be applied at two levels: around a whole `Connector` to
+/// bound resolve-then-connect end to end, or around just the inner
+/// connect service (pairing with `TimeoutResolve` around the
+/// resolver) to give the resolve and connect phases independent
+/// budgets.
+#[derive(Clone)]
+pub struct TimeoutConnect<C> {
+    inner: C,
+    timeout: Duration,
+}
+
+impl<C> TimeoutConnect<C> {
+    pub fn new(inner: C, timeout: Duration) -> Self {
+        TimeoutConnect { inner, timeout }
+    }
+}
+
+impl<C, Target> ConnectService<Target> for TimeoutConnect<C>
+where
+    C: ConnectService<Target>,
+{
+    type Response = C::Response;
+    type Error = TimeoutConnectError<C::Error>;
+    type Future = TimeoutConnectFuture<C::Future>;
+
+    fn poll_ready(&mut self) -> Poll<(), Self::Error> {
+        self.inner.poll_ready().map_err(TimeoutConnectError::Connect)
+    }
+
+    fn connect(&mut self, target: Target) -> Self::Future {
+        TimeoutConnectFuture {
+            inner: self.inner.connect(target),
+            delay: Delay::new(Instant::now() + self.timeout),
+        }
+    }
+}
+
+pub enum TimeoutConnectError<E> {
+    Connect(E),
+    Timeout,
+}
+
+pub struct TimeoutConnectFuture<F> {
+    inner: F,
+    delay: Delay,
+}
+
+impl<F> Future for TimeoutConnectFuture<F>
+where
+    F: Future,
+{
+    type Item = F::Item;
+    type Error = TimeoutConnectError<F::Error>;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        match self.inner.poll() {
+            Ok(Async::Ready(item)) => return Ok(Async::Ready(item)),
+            Ok(Async::NotReady) => {}
+            Err(e) => return Err(TimeoutConnectError::Connect(e)),
+        }
+
+        match self.delay.poll() {
+            Ok(Async::Ready(())) => Err(TimeoutConnectError::Timeout),
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            // A broken timer shouldn't fail the connect; keep waiting on
+            // the inner future instead.
+            Err(_) => Ok(Async::NotReady),
+        }
+    }
+}
+
+/// Bounds how long a `Resolve` is allowed to take. Pairs with
+/// `TimeoutConnect` to give the resolve phase its own budget, distinct
+/// from the connect phase.
+#[derive(Clone)]
+pub struct TimeoutResolve<R> {
+    inner: R,
+    timeout: Duration,
+}
+
+impl<R> TimeoutResolve<R> {
+    pub fn new(inner: R, timeout: Duration) -> Self {
+        TimeoutResolve { inner, timeout }
+    }
+}
+
+impl<R, Target> Resolve<Target> for TimeoutResolve<R>
+where
+    R: Resolve<Target>,
+{
+    type Error = TimeoutResolveError<R::Error>;
+    type Future = TimeoutResolveFuture<R::Future>;
+
+    fn poll_ready(&mut self) -> Poll<(), Self::Error> {
+        self.inner.poll_ready().map_err(TimeoutResolveError::Resolve)
+    }
+
+    fn lookup(&mut self, target: Target) -> Self::Future {
+        TimeoutResolveFuture {
+            inner: self.inner.lookup(target),
+            delay: Delay::new(Instant::now() + self.timeout),
+        }
+    }
+}
+
+pub enum TimeoutResolveError<E> {
+    Resolve(E),
+    Timeout,
+}
+
+pub struct TimeoutResolveFuture<F> {
+    inner: F,
+    delay: Delay,
+}
+
+impl<F> Future for TimeoutResolveFuture<F>
+where
+    F: Future,
+{
+    type Item = F::Item;
+    type Error = TimeoutResolveError<F::Error>;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        match self.inner.poll() {
+            Ok(Async::Ready(item)) => return Ok(Async::Ready(item)),
+            Ok(Async::NotReady) => {}
+            Err(e) => return Err(TimeoutResolveError::Resolve(e)),
+        }
+
+        match self.delay.poll() {
+            Ok(Async::Ready(())) => Err(TimeoutResolveError::Timeout),
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            Err(_) => Ok(Async::NotReady),
+        }
+    }
+}